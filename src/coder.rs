@@ -0,0 +1,1093 @@
+//! Encoders and decoders, doing the actual work of compressing and
+//! decompressing audio data.
+use crate::{
+    error::{check, Error, Result},
+    ffi,
+    packet::Packet,
+    Application, Bandwidth, Bitrate, Channels, FrameDuration, MutSignals, SampleRate, Signal,
+};
+use std::convert::{TryFrom, TryInto};
+
+/// Validates a packet-loss percentage before it is handed to
+/// `OPUS_SET_PACKET_LOSS_PERC`, which only accepts `0..=100`.
+fn validate_percent(percent: u8) -> Result<()> {
+    if percent > 100 {
+        return Err(Error::InvalidArgument);
+    }
+
+    Ok(())
+}
+
+/// Validates an encoder complexity before it is handed to
+/// `OPUS_SET_COMPLEXITY`, which only accepts `0..=10`.
+fn validate_complexity(complexity: u8) -> Result<()> {
+    if complexity > 10 {
+        return Err(Error::InvalidArgument);
+    }
+
+    Ok(())
+}
+
+/// Validates an LSB depth before it is handed to `OPUS_SET_LSB_DEPTH`,
+/// which only accepts `8..=24`.
+fn validate_lsb_depth(depth: i32) -> Result<()> {
+    if !(8..=24).contains(&depth) {
+        return Err(Error::InvalidArgument);
+    }
+
+    Ok(())
+}
+
+/// Encodes raw PCM-signals into Opus-packets.
+#[derive(Debug)]
+pub struct Encoder {
+    pointer: *mut ffi::OpusEncoder,
+    channels: Channels,
+}
+
+// SAFETY: An `ffi::OpusEncoder` does not rely on thread-local state and may
+// freely be moved to and used from another thread, as long as access is
+// synchronised, which `&mut self` on every mutating method already ensures.
+unsafe impl Send for Encoder {}
+
+impl Encoder {
+    /// Creates a new encoder for the given sample rate, channel count and
+    /// application.
+    pub fn new(sample_rate: SampleRate, channels: Channels, mode: Application) -> Result<Self> {
+        let mut error = 0;
+
+        let pointer = unsafe {
+            ffi::opus_encoder_create(sample_rate as i32, channels.into(), mode as i32, &mut error)
+        };
+
+        check(error)?;
+
+        Ok(Self { pointer, channels })
+    }
+
+    pub fn channels(&self) -> Channels {
+        self.channels
+    }
+
+    /// Encodes an `i16`-signal, writing the resulting Opus-packet into
+    /// `output` and returning the number of bytes written.
+    pub fn encode(&mut self, input: &[i16], mut output: MutSignals<'_, u8>) -> Result<usize> {
+        let frame_size = input.len() / self.channels as usize;
+
+        let len = check(unsafe {
+            ffi::opus_encode(
+                self.pointer,
+                input.as_ptr(),
+                frame_size as i32,
+                output.as_mut_ptr(),
+                output.i32_len(),
+            )
+        })?;
+
+        Ok(len as usize)
+    }
+
+    /// Encodes a floating point signal, writing the resulting Opus-packet
+    /// into `output` and returning the number of bytes written.
+    pub fn encode_float(&mut self, input: &[f32], mut output: MutSignals<'_, u8>) -> Result<usize> {
+        let frame_size = input.len() / self.channels as usize;
+
+        let len = check(unsafe {
+            ffi::opus_encode_float(
+                self.pointer,
+                input.as_ptr(),
+                frame_size as i32,
+                output.as_mut_ptr(),
+                output.i32_len(),
+            )
+        })?;
+
+        Ok(len as usize)
+    }
+
+    /// Sets the bitrate this encoder targets.
+    pub fn set_bitrate(&mut self, bitrate: Bitrate) -> Result<()> {
+        check(unsafe {
+            ffi::opus_encoder_ctl(self.pointer, ffi::OPUS_SET_BITRATE_REQUEST, i32::from(bitrate))
+        })?;
+
+        Ok(())
+    }
+
+    /// Gets the bitrate this encoder currently targets.
+    pub fn bitrate(&self) -> Result<Bitrate> {
+        let mut value = 0;
+
+        check(unsafe {
+            ffi::opus_encoder_ctl(self.pointer, ffi::OPUS_GET_BITRATE_REQUEST, &mut value)
+        })?;
+
+        value.try_into()
+    }
+
+    /// Hints the encoder about the type of signal being encoded, letting it
+    /// tune internal parameters towards it.
+    pub fn set_signal(&mut self, signal: Signal) -> Result<()> {
+        check(unsafe {
+            ffi::opus_encoder_ctl(self.pointer, ffi::OPUS_SET_SIGNAL_REQUEST, signal as i32)
+        })?;
+
+        Ok(())
+    }
+
+    /// Gets the signal-type hint currently configured.
+    pub fn signal(&self) -> Result<Signal> {
+        let mut value = 0;
+
+        check(unsafe {
+            ffi::opus_encoder_ctl(self.pointer, ffi::OPUS_GET_SIGNAL_REQUEST, &mut value)
+        })?;
+
+        Signal::try_from(value)
+    }
+
+    /// Sets the encoder's intended bandwidth.
+    pub fn set_bandwidth(&mut self, bandwidth: Bandwidth) -> Result<()> {
+        check(unsafe {
+            ffi::opus_encoder_ctl(self.pointer, ffi::OPUS_SET_BANDWIDTH_REQUEST, bandwidth as i32)
+        })?;
+
+        Ok(())
+    }
+
+    /// Gets the encoder's intended bandwidth.
+    pub fn bandwidth(&self) -> Result<Bandwidth> {
+        let mut value = 0;
+
+        check(unsafe {
+            ffi::opus_encoder_ctl(self.pointer, ffi::OPUS_GET_BANDWIDTH_REQUEST, &mut value)
+        })?;
+
+        Bandwidth::try_from(value)
+    }
+
+    /// Caps the bandwidth the encoder may choose automatically, without
+    /// forcing it to a fixed value the way [`set_bandwidth`](Self::set_bandwidth) does.
+    pub fn set_max_bandwidth(&mut self, bandwidth: Bandwidth) -> Result<()> {
+        check(unsafe {
+            ffi::opus_encoder_ctl(
+                self.pointer,
+                ffi::OPUS_SET_MAX_BANDWIDTH_REQUEST,
+                bandwidth as i32,
+            )
+        })?;
+
+        Ok(())
+    }
+
+    /// Gets the encoder's configured maximum bandwidth.
+    pub fn max_bandwidth(&self) -> Result<Bandwidth> {
+        let mut value = 0;
+
+        check(unsafe {
+            ffi::opus_encoder_ctl(self.pointer, ffi::OPUS_GET_MAX_BANDWIDTH_REQUEST, &mut value)
+        })?;
+
+        Bandwidth::try_from(value)
+    }
+
+    /// Enables or disables variable bitrate (VBR) encoding.
+    pub fn set_vbr(&mut self, vbr: bool) -> Result<()> {
+        check(unsafe {
+            ffi::opus_encoder_ctl(self.pointer, ffi::OPUS_SET_VBR_REQUEST, i32::from(vbr))
+        })?;
+
+        Ok(())
+    }
+
+    /// Gets whether variable bitrate (VBR) encoding is enabled.
+    pub fn vbr(&self) -> Result<bool> {
+        let mut value = 0;
+
+        check(unsafe { ffi::opus_encoder_ctl(self.pointer, ffi::OPUS_GET_VBR_REQUEST, &mut value) })?;
+
+        Ok(value != 0)
+    }
+
+    /// Enables or disables constrained VBR, bounding VBR's bitrate
+    /// variance the way constant bitrate would, without giving up all of
+    /// VBR's efficiency.
+    pub fn set_vbr_constraint(&mut self, constrained: bool) -> Result<()> {
+        check(unsafe {
+            ffi::opus_encoder_ctl(
+                self.pointer,
+                ffi::OPUS_SET_VBR_CONSTRAINT_REQUEST,
+                i32::from(constrained),
+            )
+        })?;
+
+        Ok(())
+    }
+
+    /// Gets whether constrained VBR is enabled.
+    pub fn vbr_constraint(&self) -> Result<bool> {
+        let mut value = 0;
+
+        check(unsafe {
+            ffi::opus_encoder_ctl(self.pointer, ffi::OPUS_GET_VBR_CONSTRAINT_REQUEST, &mut value)
+        })?;
+
+        Ok(value != 0)
+    }
+
+    /// Enables or disables in-band forward error correction (FEC). An
+    /// encoder with FEC enabled embeds redundant information about the
+    /// previous frame into the current one, letting a decoder reconstruct
+    /// a lost frame via [`Decoder::decode`] with `fec` set.
+    ///
+    /// [`Decoder::decode`]: crate::coder::Decoder::decode
+    pub fn set_inband_fec(&mut self, fec: bool) -> Result<()> {
+        check(unsafe {
+            ffi::opus_encoder_ctl(self.pointer, ffi::OPUS_SET_INBAND_FEC_REQUEST, i32::from(fec))
+        })?;
+
+        Ok(())
+    }
+
+    /// Gets whether in-band FEC is enabled.
+    pub fn inband_fec(&self) -> Result<bool> {
+        let mut value = 0;
+
+        check(unsafe {
+            ffi::opus_encoder_ctl(self.pointer, ffi::OPUS_GET_INBAND_FEC_REQUEST, &mut value)
+        })?;
+
+        Ok(value != 0)
+    }
+
+    /// Sets the expected packet-loss percentage of the transport this
+    /// stream travels over, `0..=100`. Opus uses this to decide how much
+    /// redundancy in-band FEC should add.
+    pub fn set_packet_loss_perc(&mut self, percent: u8) -> Result<()> {
+        validate_percent(percent)?;
+
+        check(unsafe {
+            ffi::opus_encoder_ctl(
+                self.pointer,
+                ffi::OPUS_SET_PACKET_LOSS_PERC_REQUEST,
+                i32::from(percent),
+            )
+        })?;
+
+        Ok(())
+    }
+
+    /// Gets the configured expected packet-loss percentage.
+    pub fn packet_loss_perc(&self) -> Result<u8> {
+        let mut value = 0;
+
+        check(unsafe {
+            ffi::opus_encoder_ctl(self.pointer, ffi::OPUS_GET_PACKET_LOSS_PERC_REQUEST, &mut value)
+        })?;
+
+        Ok(value as u8)
+    }
+
+    /// Sets the encoder's computational complexity, `0..=10`, trading CPU
+    /// usage for quality.
+    pub fn set_complexity(&mut self, complexity: u8) -> Result<()> {
+        validate_complexity(complexity)?;
+
+        check(unsafe {
+            ffi::opus_encoder_ctl(
+                self.pointer,
+                ffi::OPUS_SET_COMPLEXITY_REQUEST,
+                i32::from(complexity),
+            )
+        })?;
+
+        Ok(())
+    }
+
+    /// Gets the encoder's configured computational complexity.
+    pub fn complexity(&self) -> Result<u8> {
+        let mut value = 0;
+
+        check(unsafe {
+            ffi::opus_encoder_ctl(self.pointer, ffi::OPUS_GET_COMPLEXITY_REQUEST, &mut value)
+        })?;
+
+        Ok(value as u8)
+    }
+
+    /// Enables or disables discontinuous transmission (DTX), letting the
+    /// encoder stop producing packets for silence or background noise.
+    pub fn set_dtx(&mut self, dtx: bool) -> Result<()> {
+        check(unsafe {
+            ffi::opus_encoder_ctl(self.pointer, ffi::OPUS_SET_DTX_REQUEST, i32::from(dtx))
+        })?;
+
+        Ok(())
+    }
+
+    /// Gets whether DTX is enabled.
+    pub fn dtx(&self) -> Result<bool> {
+        let mut value = 0;
+
+        check(unsafe { ffi::opus_encoder_ctl(self.pointer, ffi::OPUS_GET_DTX_REQUEST, &mut value) })?;
+
+        Ok(value != 0)
+    }
+
+    /// Sets the least significant bits the input signal actually carries,
+    /// `8..=24`, letting the encoder dither accordingly instead of assuming
+    /// full 24-bit depth.
+    pub fn set_lsb_depth(&mut self, depth: i32) -> Result<()> {
+        validate_lsb_depth(depth)?;
+
+        check(unsafe {
+            ffi::opus_encoder_ctl(self.pointer, ffi::OPUS_SET_LSB_DEPTH_REQUEST, depth)
+        })?;
+
+        Ok(())
+    }
+
+    /// Gets the configured LSB depth.
+    pub fn lsb_depth(&self) -> Result<i32> {
+        let mut value = 0;
+
+        check(unsafe {
+            ffi::opus_encoder_ctl(self.pointer, ffi::OPUS_GET_LSB_DEPTH_REQUEST, &mut value)
+        })?;
+
+        Ok(value)
+    }
+
+    /// Disables the use of linear prediction, mostly useful when encoding
+    /// non-speech/music signals like DTMF tones.
+    pub fn set_prediction_disabled(&mut self, disabled: bool) -> Result<()> {
+        check(unsafe {
+            ffi::opus_encoder_ctl(
+                self.pointer,
+                ffi::OPUS_SET_PREDICTION_DISABLED_REQUEST,
+                i32::from(disabled),
+            )
+        })?;
+
+        Ok(())
+    }
+
+    /// Gets whether linear prediction is disabled.
+    pub fn prediction_disabled(&self) -> Result<bool> {
+        let mut value = 0;
+
+        check(unsafe {
+            ffi::opus_encoder_ctl(
+                self.pointer,
+                ffi::OPUS_GET_PREDICTION_DISABLED_REQUEST,
+                &mut value,
+            )
+        })?;
+
+        Ok(value != 0)
+    }
+
+    /// Forces the encoder to use the given number of channels, regardless
+    /// of the channel count it was constructed with, or [`Channels::Auto`]
+    /// to let it decide per-frame.
+    pub fn set_force_channels(&mut self, channels: Channels) -> Result<()> {
+        check(unsafe {
+            ffi::opus_encoder_ctl(
+                self.pointer,
+                ffi::OPUS_SET_FORCE_CHANNELS_REQUEST,
+                channels as i32,
+            )
+        })?;
+
+        Ok(())
+    }
+
+    /// Gets the encoder's forced channel count, if any.
+    pub fn force_channels(&self) -> Result<Channels> {
+        let mut value = 0;
+
+        check(unsafe {
+            ffi::opus_encoder_ctl(self.pointer, ffi::OPUS_GET_FORCE_CHANNELS_REQUEST, &mut value)
+        })?;
+
+        Channels::try_from(value)
+    }
+
+    /// Pins the encoder to a fixed frame duration instead of letting it pick
+    /// one per call to [`encode`](Self::encode)/[`encode_float`](Self::encode_float).
+    /// Durations beyond 60ms are only available in CELT-only ("expert") mode.
+    pub fn set_expert_frame_duration(&mut self, duration: FrameDuration) -> Result<()> {
+        check(unsafe {
+            ffi::opus_encoder_ctl(
+                self.pointer,
+                ffi::OPUS_SET_EXPERT_FRAME_DURATION_REQUEST,
+                duration as i32,
+            )
+        })?;
+
+        Ok(())
+    }
+
+    /// Gets the encoder's configured frame duration.
+    pub fn expert_frame_duration(&self) -> Result<FrameDuration> {
+        let mut value = 0;
+
+        check(unsafe {
+            ffi::opus_encoder_ctl(
+                self.pointer,
+                ffi::OPUS_GET_EXPERT_FRAME_DURATION_REQUEST,
+                &mut value,
+            )
+        })?;
+
+        FrameDuration::try_from(value)
+    }
+
+    /// Gets the number of samples of lookahead (encoder delay) the encoder
+    /// introduces, e.g. to fill in an Ogg Opus header's
+    /// [`pre_skip`](crate::ogg::OpusHead::pre_skip).
+    pub fn lookahead(&self) -> Result<i32> {
+        let mut value = 0;
+
+        check(unsafe {
+            ffi::opus_encoder_ctl(self.pointer, ffi::OPUS_GET_LOOKAHEAD_REQUEST, &mut value)
+        })?;
+
+        Ok(value)
+    }
+
+    /// Gets the encoder's final range, a value that is bit-exactly
+    /// reproducible for the same input on the same libopus version. Lets
+    /// two implementations cross-check they produced identical output.
+    pub fn final_range(&self) -> Result<u32> {
+        let mut value = 0;
+
+        check(unsafe {
+            ffi::opus_encoder_ctl(self.pointer, ffi::OPUS_GET_FINAL_RANGE_REQUEST, &mut value)
+        })?;
+
+        Ok(value as u32)
+    }
+
+    /// Gets the sample rate this encoder was created with.
+    pub fn sample_rate(&self) -> Result<SampleRate> {
+        let mut value = 0;
+
+        check(unsafe {
+            ffi::opus_encoder_ctl(self.pointer, ffi::OPUS_GET_SAMPLE_RATE_REQUEST, &mut value)
+        })?;
+
+        SampleRate::try_from(value)
+    }
+
+    /// Resets the encoder to its initial state, discarding any internal
+    /// memory of past frames as if it had just been created.
+    pub fn reset_state(&mut self) -> Result<()> {
+        check(unsafe { ffi::opus_encoder_ctl(self.pointer, ffi::OPUS_RESET_STATE_REQUEST) })?;
+
+        Ok(())
+    }
+}
+
+impl Drop for Encoder {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::opus_encoder_destroy(self.pointer);
+        }
+    }
+}
+
+/// Turns an optional packet into the `(data, len)` pair `opus_decode`
+/// expects, passing a null pointer and zero length for `None` to trigger
+/// packet-loss concealment.
+fn packet_ptr_and_len(input: Option<Packet<'_>>) -> (*const u8, i32) {
+    match input {
+        Some(packet) => (packet.as_ptr(), packet.i32_len()),
+        None => (std::ptr::null(), 0),
+    }
+}
+
+/// Decodes Opus-packets back into raw PCM-signals.
+#[derive(Debug)]
+pub struct Decoder {
+    pointer: *mut ffi::OpusDecoder,
+    channels: Channels,
+}
+
+// SAFETY: see the comment on `Encoder`'s `Send`-impl above, the same reasoning
+// applies to `ffi::OpusDecoder`.
+unsafe impl Send for Decoder {}
+
+impl Decoder {
+    /// Creates a new decoder for the given sample rate and channel count.
+    pub fn new(sample_rate: SampleRate, channels: Channels) -> Result<Self> {
+        let mut error = 0;
+
+        let pointer =
+            unsafe { ffi::opus_decoder_create(sample_rate as i32, channels.into(), &mut error) };
+
+        check(error)?;
+
+        Ok(Self { pointer, channels })
+    }
+
+    pub fn channels(&self) -> Channels {
+        self.channels
+    }
+
+    /// Decodes a packet into an `i16`-signal, returning the number of
+    /// samples (per channel) written to `output`.
+    ///
+    /// Passing `None` for `input` runs pure packet-loss concealment (PLC),
+    /// synthesising a replacement for a frame that was never received;
+    /// `output`'s length still determines the frame size to synthesise.
+    ///
+    /// Setting `fec` reconstructs the *previous* frame from the in-band
+    /// forward error correction data carried by `input`, which must be the
+    /// next packet actually received after the loss, from an encoder that
+    /// had [`Encoder::set_inband_fec`] enabled. `output` must be sized for
+    /// the lost frame's duration, not `input`'s own frame size.
+    ///
+    /// [`Encoder::set_inband_fec`]: crate::coder::Encoder::set_inband_fec
+    pub fn decode(
+        &mut self,
+        input: Option<Packet<'_>>,
+        mut output: MutSignals<'_, i16>,
+        fec: bool,
+    ) -> Result<usize> {
+        let frame_size = output.i32_len() / self.channels as i32;
+        let (data, data_len) = packet_ptr_and_len(input);
+
+        let len = check(unsafe {
+            ffi::opus_decode(
+                self.pointer,
+                data,
+                data_len,
+                output.as_mut_ptr(),
+                frame_size,
+                i32::from(fec),
+            )
+        })?;
+
+        Ok(len as usize)
+    }
+
+    /// Decodes a packet into a floating point signal, returning the number
+    /// of samples (per channel) written to `output`.
+    ///
+    /// See [`decode`](Self::decode) for the meaning of `input: None` (PLC)
+    /// and `fec` (in-band FEC reconstruction of the previous lost frame).
+    pub fn decode_float(
+        &mut self,
+        input: Option<Packet<'_>>,
+        mut output: MutSignals<'_, f32>,
+        fec: bool,
+    ) -> Result<usize> {
+        let frame_size = output.i32_len() / self.channels as i32;
+        let (data, data_len) = packet_ptr_and_len(input);
+
+        let len = check(unsafe {
+            ffi::opus_decode_float(
+                self.pointer,
+                data,
+                data_len,
+                output.as_mut_ptr(),
+                frame_size,
+                i32::from(fec),
+            )
+        })?;
+
+        Ok(len as usize)
+    }
+
+    /// Disables phase inversion for intensity stereo, which can benefit
+    /// the quality of mono downmixes at the cost of stereo separation.
+    pub fn set_phase_inversion_disabled(&mut self, disabled: bool) -> Result<()> {
+        check(unsafe {
+            ffi::opus_decoder_ctl(
+                self.pointer,
+                ffi::OPUS_SET_PHASE_INVERSION_DISABLED_REQUEST,
+                i32::from(disabled),
+            )
+        })?;
+
+        Ok(())
+    }
+
+    /// Gets whether phase inversion is disabled.
+    pub fn phase_inversion_disabled(&self) -> Result<bool> {
+        let mut value = 0;
+
+        check(unsafe {
+            ffi::opus_decoder_ctl(
+                self.pointer,
+                ffi::OPUS_GET_PHASE_INVERSION_DISABLED_REQUEST,
+                &mut value,
+            )
+        })?;
+
+        Ok(value != 0)
+    }
+
+    /// Sets the least significant bits the output signal actually needs,
+    /// `8..=24`, letting the decoder skip dithering it does not need.
+    pub fn set_lsb_depth(&mut self, depth: i32) -> Result<()> {
+        validate_lsb_depth(depth)?;
+
+        check(unsafe {
+            ffi::opus_decoder_ctl(self.pointer, ffi::OPUS_SET_LSB_DEPTH_REQUEST, depth)
+        })?;
+
+        Ok(())
+    }
+
+    /// Gets the configured LSB depth.
+    pub fn lsb_depth(&self) -> Result<i32> {
+        let mut value = 0;
+
+        check(unsafe {
+            ffi::opus_decoder_ctl(self.pointer, ffi::OPUS_GET_LSB_DEPTH_REQUEST, &mut value)
+        })?;
+
+        Ok(value)
+    }
+
+    /// Gets the decoder's final range, a value that is bit-exactly
+    /// reproducible for the same input on the same libopus version. Lets
+    /// two implementations cross-check they produced identical output, e.g.
+    /// against [`Encoder::final_range`].
+    pub fn final_range(&self) -> Result<u32> {
+        let mut value = 0;
+
+        check(unsafe {
+            ffi::opus_decoder_ctl(self.pointer, ffi::OPUS_GET_FINAL_RANGE_REQUEST, &mut value)
+        })?;
+
+        Ok(value as u32)
+    }
+
+    /// Gets the sample rate this decoder was created with.
+    pub fn sample_rate(&self) -> Result<SampleRate> {
+        let mut value = 0;
+
+        check(unsafe {
+            ffi::opus_decoder_ctl(self.pointer, ffi::OPUS_GET_SAMPLE_RATE_REQUEST, &mut value)
+        })?;
+
+        SampleRate::try_from(value)
+    }
+
+    /// Resets the decoder to its initial state, discarding any internal
+    /// memory of past frames as if it had just been created.
+    pub fn reset_state(&mut self) -> Result<()> {
+        check(unsafe { ffi::opus_decoder_ctl(self.pointer, ffi::OPUS_RESET_STATE_REQUEST) })?;
+
+        Ok(())
+    }
+}
+
+impl Drop for Decoder {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::opus_decoder_destroy(self.pointer);
+        }
+    }
+}
+
+/// Encodes multiple synchronised Opus streams making up one multichannel
+/// signal, e.g. 5.1 or 7.1 surround sound.
+///
+/// A multistream is a set of independent Opus streams, some of which
+/// ("coupled streams") carry two channels instead of one, plus a mapping
+/// from input channel to decoded channel. This lets Opus encode more than
+/// the two channels [`Encoder`] supports while still reusing its mono and
+/// stereo coding internally.
+#[derive(Debug)]
+pub struct MultistreamEncoder {
+    pointer: *mut ffi::OpusMSEncoder,
+    channels: usize,
+}
+
+// SAFETY: see the comment on `Encoder`'s `Send`-impl above, the same
+// reasoning applies to `ffi::OpusMSEncoder`.
+unsafe impl Send for MultistreamEncoder {}
+
+impl MultistreamEncoder {
+    /// Creates a new multistream encoder from an explicit channel mapping.
+    ///
+    /// `streams` is the total number of Opus streams to encode, of which
+    /// `coupled_streams` carry two channels instead of one. `mapping` has
+    /// one entry per input channel, giving the index of the decoded channel
+    /// it should be taken from; `255` marks a channel as silent.
+    pub fn new(
+        sample_rate: SampleRate,
+        channels: usize,
+        streams: i32,
+        coupled_streams: i32,
+        mapping: &[u8],
+        application: Application,
+    ) -> Result<Self> {
+        if mapping.len() != channels {
+            return Err(Error::InvalidArgument);
+        }
+
+        let mut error = 0;
+
+        let pointer = unsafe {
+            ffi::opus_multistream_encoder_create(
+                sample_rate as i32,
+                channels as i32,
+                streams,
+                coupled_streams,
+                mapping.as_ptr(),
+                application as i32,
+                &mut error,
+            )
+        };
+
+        check(error)?;
+
+        Ok(Self { pointer, channels })
+    }
+
+    /// Creates a new multistream encoder for a standard surround layout,
+    /// letting Opus derive the stream count and channel mapping for you.
+    ///
+    /// `mapping_family` follows the Ogg Opus convention: `0` for mono/stereo,
+    /// `1` for the Vorbis channel order (up to 8 channels), `255` for raw,
+    /// unidentified channels. Returns the encoder alongside the `streams`,
+    /// `coupled_streams` and `mapping` Opus picked, so callers can write
+    /// them into a container header.
+    pub fn new_surround(
+        sample_rate: SampleRate,
+        channels: usize,
+        mapping_family: i32,
+        application: Application,
+    ) -> Result<(Self, i32, i32, Vec<u8>)> {
+        let mut error = 0;
+        let mut streams = 0;
+        let mut coupled_streams = 0;
+        let mut mapping = vec![0u8; channels];
+
+        let pointer = unsafe {
+            ffi::opus_multistream_surround_encoder_create(
+                sample_rate as i32,
+                channels as i32,
+                mapping_family,
+                &mut streams,
+                &mut coupled_streams,
+                mapping.as_mut_ptr(),
+                application as i32,
+                &mut error,
+            )
+        };
+
+        check(error)?;
+
+        Ok((Self { pointer, channels }, streams, coupled_streams, mapping))
+    }
+
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Encodes an `i16`-signal, writing the resulting Opus-packet into
+    /// `output` and returning the number of bytes written.
+    pub fn encode(&mut self, input: &[i16], mut output: MutSignals<'_, u8>) -> Result<usize> {
+        let frame_size = input.len() / self.channels;
+
+        let len = check(unsafe {
+            ffi::opus_multistream_encode(
+                self.pointer,
+                input.as_ptr(),
+                frame_size as i32,
+                output.as_mut_ptr(),
+                output.i32_len(),
+            )
+        })?;
+
+        Ok(len as usize)
+    }
+
+    /// Encodes a floating point signal, writing the resulting Opus-packet
+    /// into `output` and returning the number of bytes written.
+    pub fn encode_float(&mut self, input: &[f32], mut output: MutSignals<'_, u8>) -> Result<usize> {
+        let frame_size = input.len() / self.channels;
+
+        let len = check(unsafe {
+            ffi::opus_multistream_encode_float(
+                self.pointer,
+                input.as_ptr(),
+                frame_size as i32,
+                output.as_mut_ptr(),
+                output.i32_len(),
+            )
+        })?;
+
+        Ok(len as usize)
+    }
+}
+
+impl Drop for MultistreamEncoder {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::opus_multistream_encoder_destroy(self.pointer);
+        }
+    }
+}
+
+/// Decodes multiple synchronised Opus streams back into one multichannel
+/// signal.
+#[derive(Debug)]
+pub struct MultistreamDecoder {
+    pointer: *mut ffi::OpusMSDecoder,
+    channels: usize,
+}
+
+// SAFETY: see the comment on `Encoder`'s `Send`-impl above, the same
+// reasoning applies to `ffi::OpusMSDecoder`.
+unsafe impl Send for MultistreamDecoder {}
+
+impl MultistreamDecoder {
+    /// Creates a new multistream decoder. `streams`, `coupled_streams` and
+    /// `mapping` must match the encoder (or container header) this stream
+    /// came from.
+    pub fn new(
+        sample_rate: SampleRate,
+        channels: usize,
+        streams: i32,
+        coupled_streams: i32,
+        mapping: &[u8],
+    ) -> Result<Self> {
+        if mapping.len() != channels {
+            return Err(Error::InvalidArgument);
+        }
+
+        let mut error = 0;
+
+        let pointer = unsafe {
+            ffi::opus_multistream_decoder_create(
+                sample_rate as i32,
+                channels as i32,
+                streams,
+                coupled_streams,
+                mapping.as_ptr(),
+                &mut error,
+            )
+        };
+
+        check(error)?;
+
+        Ok(Self { pointer, channels })
+    }
+
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Decodes a packet into an `i16`-signal, returning the number of
+    /// samples (per channel) written to `output`.
+    ///
+    /// See [`Decoder::decode`] for the meaning of `input: None` (PLC) and
+    /// `fec` (in-band FEC reconstruction of the previous lost frame).
+    ///
+    /// [`Decoder::decode`]: crate::coder::Decoder::decode
+    pub fn decode(
+        &mut self,
+        input: Option<Packet<'_>>,
+        mut output: MutSignals<'_, i16>,
+        fec: bool,
+    ) -> Result<usize> {
+        let frame_size = output.i32_len() / self.channels as i32;
+        let (data, data_len) = packet_ptr_and_len(input);
+
+        let len = check(unsafe {
+            ffi::opus_multistream_decode(
+                self.pointer,
+                data,
+                data_len,
+                output.as_mut_ptr(),
+                frame_size,
+                i32::from(fec),
+            )
+        })?;
+
+        Ok(len as usize)
+    }
+
+    /// Decodes a packet into a floating point signal, returning the number
+    /// of samples (per channel) written to `output`.
+    ///
+    /// See [`Decoder::decode`] for the meaning of `input: None` (PLC) and
+    /// `fec` (in-band FEC reconstruction of the previous lost frame).
+    ///
+    /// [`Decoder::decode`]: crate::coder::Decoder::decode
+    pub fn decode_float(
+        &mut self,
+        input: Option<Packet<'_>>,
+        mut output: MutSignals<'_, f32>,
+        fec: bool,
+    ) -> Result<usize> {
+        let frame_size = output.i32_len() / self.channels as i32;
+        let (data, data_len) = packet_ptr_and_len(input);
+
+        let len = check(unsafe {
+            ffi::opus_multistream_decode_float(
+                self.pointer,
+                data,
+                data_len,
+                output.as_mut_ptr(),
+                frame_size,
+                i32::from(fec),
+            )
+        })?;
+
+        Ok(len as usize)
+    }
+}
+
+impl Drop for MultistreamDecoder {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::opus_multistream_decoder_destroy(self.pointer);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        packet_ptr_and_len, validate_complexity, validate_lsb_depth, validate_percent, Decoder,
+        Encoder, MultistreamDecoder, MultistreamEncoder,
+    };
+    use crate::{error::Error, packet::Packet, Application, Channels, MutSignals, SampleRate};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn packet_ptr_and_len_none_is_null_plc_request() {
+        let (data, len) = packet_ptr_and_len(None);
+
+        assert!(data.is_null());
+        assert_eq!(len, 0);
+    }
+
+    #[test]
+    fn packet_ptr_and_len_some_forwards_packet() {
+        let bytes = [1, 2, 3, 4];
+        let packet = Packet::try_from(&bytes[..]).unwrap();
+
+        let (data, len) = packet_ptr_and_len(Some(packet));
+
+        assert_eq!(data, bytes.as_ptr());
+        assert_eq!(len, 4);
+    }
+
+    #[test]
+    fn validate_percent_accepts_0_to_100() {
+        assert!(validate_percent(0).is_ok());
+        assert!(validate_percent(100).is_ok());
+        assert_eq!(validate_percent(101).err(), Some(Error::InvalidArgument));
+    }
+
+    #[test]
+    fn validate_complexity_accepts_0_to_10() {
+        assert!(validate_complexity(0).is_ok());
+        assert!(validate_complexity(10).is_ok());
+        assert_eq!(validate_complexity(11).err(), Some(Error::InvalidArgument));
+    }
+
+    #[test]
+    fn validate_lsb_depth_accepts_8_to_24() {
+        assert!(validate_lsb_depth(8).is_ok());
+        assert!(validate_lsb_depth(24).is_ok());
+        assert_eq!(validate_lsb_depth(7).err(), Some(Error::InvalidArgument));
+        assert_eq!(validate_lsb_depth(25).err(), Some(Error::InvalidArgument));
+    }
+
+    #[test]
+    fn encoder_decoder_round_trip_recovers_frame_size() {
+        let mut encoder =
+            Encoder::new(SampleRate::Hz48000, Channels::Mono, Application::Voip).unwrap();
+        let mut decoder = Decoder::new(SampleRate::Hz48000, Channels::Mono).unwrap();
+
+        let input = vec![0i16; 960];
+        let mut packet = vec![0u8; 4000];
+        let written = encoder
+            .encode(&input, MutSignals::try_from(&mut packet).unwrap())
+            .unwrap();
+
+        let mut output = vec![0i16; 960];
+        let samples = decoder
+            .decode(
+                Some(Packet::try_from(&packet[..written]).unwrap()),
+                MutSignals::try_from(&mut output).unwrap(),
+                false,
+            )
+            .unwrap();
+
+        assert_eq!(samples, 960);
+    }
+
+    #[test]
+    fn decoder_plc_synthesises_a_frame_for_a_missing_packet() {
+        let mut decoder = Decoder::new(SampleRate::Hz48000, Channels::Mono).unwrap();
+
+        let mut output = vec![0i16; 960];
+        let samples = decoder
+            .decode(None, MutSignals::try_from(&mut output).unwrap(), false)
+            .unwrap();
+
+        assert_eq!(samples, 960);
+    }
+
+    #[test]
+    fn decoder_fec_reconstructs_the_previous_frame() {
+        let mut encoder =
+            Encoder::new(SampleRate::Hz48000, Channels::Mono, Application::Voip).unwrap();
+        encoder.set_inband_fec(true).unwrap();
+        encoder.set_packet_loss_perc(50).unwrap();
+
+        let mut decoder = Decoder::new(SampleRate::Hz48000, Channels::Mono).unwrap();
+
+        let frame = vec![0i16; 960];
+        let mut packet = vec![0u8; 4000];
+
+        // Encode a second frame so its packet carries FEC data that can
+        // reconstruct the first, simulating the first packet never arriving.
+        encoder
+            .encode(&frame, MutSignals::try_from(&mut packet).unwrap())
+            .unwrap();
+        let written = encoder
+            .encode(&frame, MutSignals::try_from(&mut packet).unwrap())
+            .unwrap();
+
+        let mut output = vec![0i16; 960];
+        let samples = decoder
+            .decode(
+                Some(Packet::try_from(&packet[..written]).unwrap()),
+                MutSignals::try_from(&mut output).unwrap(),
+                true,
+            )
+            .unwrap();
+
+        assert_eq!(samples, 960);
+    }
+
+    // `mapping` must have one entry per channel; libopus reads exactly
+    // `channels` bytes from it, so a too-short mapping must be rejected
+    // before it ever reaches FFI.
+    #[test]
+    fn multistream_encoder_rejects_short_mapping() {
+        let result =
+            MultistreamEncoder::new(SampleRate::Hz48000, 6, 4, 2, &[0, 1], Application::Audio);
+
+        assert_eq!(result.err(), Some(Error::InvalidArgument));
+    }
+
+    #[test]
+    fn multistream_decoder_rejects_short_mapping() {
+        let result = MultistreamDecoder::new(SampleRate::Hz48000, 6, 4, 2, &[0, 1]);
+
+        assert_eq!(result.err(), Some(Error::InvalidArgument));
+    }
+}
@@ -0,0 +1,267 @@
+//! Opus Custom, a CELT-only mode for sample rates and frame sizes outside
+//! the standard Opus grid [`SampleRate`] and the main [`coder`](crate::coder)
+//! types enforce, e.g. low-latency pipelines or ultrasonic sample rates.
+//!
+//! Mirrors libopus' `opus_custom.h` API. Requires libopus to have been built
+//! with `--enable-custom-modes`, which is not the default build
+//! configuration, hence this module sitting behind the `custom` feature.
+//!
+//! [`SampleRate`]: crate::SampleRate
+use crate::{
+    error::{check, Error, Result},
+    ffi,
+    packet::Packet,
+    Channels, MutSignals,
+};
+use std::marker::PhantomData;
+
+/// Validates a sample rate and frame size before they are handed to
+/// `opus_custom_mode_create`, which expects both to be strictly positive.
+fn validate_mode_params(sample_rate: i32, frame_size: i32) -> Result<()> {
+    if sample_rate <= 0 || frame_size <= 0 {
+        return Err(Error::InvalidArgument);
+    }
+
+    Ok(())
+}
+
+/// A CELT-only mode for an arbitrary sample rate and frame size, shared by
+/// an encoder/decoder pair that must agree on it.
+#[derive(Debug)]
+pub struct CustomMode {
+    pointer: *mut ffi::OpusCustomMode,
+}
+
+// SAFETY: see the comment on `coder::Encoder`'s `Send`-impl, the same
+// reasoning applies to `ffi::OpusCustomMode`.
+unsafe impl Send for CustomMode {}
+
+impl CustomMode {
+    /// Creates a custom mode for `sample_rate` Hz and `frame_size` samples
+    /// per channel, both of which may fall outside [`SampleRate`]'s and the
+    /// main coders' usual 2.5-60ms/8-48kHz grid.
+    ///
+    /// [`SampleRate`]: crate::SampleRate
+    pub fn new(sample_rate: i32, frame_size: i32) -> Result<Self> {
+        validate_mode_params(sample_rate, frame_size)?;
+
+        let mut error = 0;
+
+        let pointer =
+            unsafe { ffi::opus_custom_mode_create(sample_rate, frame_size, &mut error) };
+
+        check(error)?;
+
+        Ok(Self { pointer })
+    }
+}
+
+impl Drop for CustomMode {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::opus_custom_mode_destroy(self.pointer);
+        }
+    }
+}
+
+/// Encodes PCM-signals using a [`CustomMode`].
+#[derive(Debug)]
+pub struct CustomEncoder<'a> {
+    pointer: *mut ffi::OpusCustomEncoder,
+    channels: Channels,
+    mode: PhantomData<&'a CustomMode>,
+}
+
+// SAFETY: see the comment on `coder::Encoder`'s `Send`-impl, the same
+// reasoning applies to `ffi::OpusCustomEncoder`.
+unsafe impl<'a> Send for CustomEncoder<'a> {}
+
+impl<'a> CustomEncoder<'a> {
+    /// Creates a new encoder bound to `mode`.
+    pub fn new(mode: &'a CustomMode, channels: Channels) -> Result<Self> {
+        let mut error = 0;
+
+        let pointer = unsafe {
+            ffi::opus_custom_encoder_create(mode.pointer, channels.into(), &mut error)
+        };
+
+        check(error)?;
+
+        Ok(Self {
+            pointer,
+            channels,
+            mode: PhantomData,
+        })
+    }
+
+    /// Encodes an `i16`-signal, writing the resulting packet into `output`
+    /// and returning the number of bytes written.
+    pub fn encode(&mut self, input: &[i16], mut output: MutSignals<'_, u8>) -> Result<usize> {
+        let frame_size = input.len() / self.channels as usize;
+
+        let len = check(unsafe {
+            ffi::opus_custom_encode(
+                self.pointer,
+                input.as_ptr(),
+                frame_size as i32,
+                output.as_mut_ptr(),
+                output.i32_len(),
+            )
+        })?;
+
+        Ok(len as usize)
+    }
+
+    /// Encodes a floating point signal, writing the resulting packet into
+    /// `output` and returning the number of bytes written.
+    pub fn encode_float(&mut self, input: &[f32], mut output: MutSignals<'_, u8>) -> Result<usize> {
+        let frame_size = input.len() / self.channels as usize;
+
+        let len = check(unsafe {
+            ffi::opus_custom_encode_float(
+                self.pointer,
+                input.as_ptr(),
+                frame_size as i32,
+                output.as_mut_ptr(),
+                output.i32_len(),
+            )
+        })?;
+
+        Ok(len as usize)
+    }
+}
+
+impl<'a> Drop for CustomEncoder<'a> {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::opus_custom_encoder_destroy(self.pointer);
+        }
+    }
+}
+
+/// Decodes packets produced by a [`CustomEncoder`] using the same
+/// [`CustomMode`].
+#[derive(Debug)]
+pub struct CustomDecoder<'a> {
+    pointer: *mut ffi::OpusCustomDecoder,
+    channels: Channels,
+    mode: PhantomData<&'a CustomMode>,
+}
+
+// SAFETY: see the comment on `coder::Encoder`'s `Send`-impl, the same
+// reasoning applies to `ffi::OpusCustomDecoder`.
+unsafe impl<'a> Send for CustomDecoder<'a> {}
+
+impl<'a> CustomDecoder<'a> {
+    /// Creates a new decoder bound to `mode`.
+    pub fn new(mode: &'a CustomMode, channels: Channels) -> Result<Self> {
+        let mut error = 0;
+
+        let pointer = unsafe {
+            ffi::opus_custom_decoder_create(mode.pointer, channels.into(), &mut error)
+        };
+
+        check(error)?;
+
+        Ok(Self {
+            pointer,
+            channels,
+            mode: PhantomData,
+        })
+    }
+
+    /// Decodes a packet into an `i16`-signal, returning the number of
+    /// samples (per channel) written to `output`.
+    pub fn decode(&mut self, input: Packet<'_>, mut output: MutSignals<'_, i16>) -> Result<usize> {
+        let frame_size = output.i32_len() / self.channels as i32;
+
+        let len = check(unsafe {
+            ffi::opus_custom_decode(
+                self.pointer,
+                input.as_ptr(),
+                input.i32_len(),
+                output.as_mut_ptr(),
+                frame_size,
+            )
+        })?;
+
+        Ok(len as usize)
+    }
+
+    /// Decodes a packet into a floating point signal, returning the number
+    /// of samples (per channel) written to `output`.
+    pub fn decode_float(
+        &mut self,
+        input: Packet<'_>,
+        mut output: MutSignals<'_, f32>,
+    ) -> Result<usize> {
+        let frame_size = output.i32_len() / self.channels as i32;
+
+        let len = check(unsafe {
+            ffi::opus_custom_decode_float(
+                self.pointer,
+                input.as_ptr(),
+                input.i32_len(),
+                output.as_mut_ptr(),
+                frame_size,
+            )
+        })?;
+
+        Ok(len as usize)
+    }
+}
+
+impl<'a> Drop for CustomDecoder<'a> {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::opus_custom_decoder_destroy(self.pointer);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{validate_mode_params, CustomDecoder, CustomEncoder, CustomMode};
+    use crate::{error::Error, packet::Packet, Channels, MutSignals};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn validate_mode_params_accepts_positive_values() {
+        assert_eq!(validate_mode_params(48000, 960), Ok(()));
+    }
+
+    #[test]
+    fn validate_mode_params_rejects_non_positive_sample_rate() {
+        assert_eq!(validate_mode_params(0, 960).err(), Some(Error::InvalidArgument));
+        assert_eq!(validate_mode_params(-1, 960).err(), Some(Error::InvalidArgument));
+    }
+
+    #[test]
+    fn validate_mode_params_rejects_non_positive_frame_size() {
+        assert_eq!(validate_mode_params(48000, 0).err(), Some(Error::InvalidArgument));
+        assert_eq!(validate_mode_params(48000, -1).err(), Some(Error::InvalidArgument));
+    }
+
+    #[test]
+    fn custom_encoder_decoder_round_trip_recovers_frame_size() {
+        let mode = CustomMode::new(48000, 960).unwrap();
+        let mut encoder = CustomEncoder::new(&mode, Channels::Mono).unwrap();
+        let mut decoder = CustomDecoder::new(&mode, Channels::Mono).unwrap();
+
+        let input = vec![0i16; 960];
+        let mut packet = vec![0u8; 4000];
+        let written = encoder
+            .encode(&input, MutSignals::try_from(&mut packet).unwrap())
+            .unwrap();
+
+        let mut output = vec![0i16; 960];
+        let samples = decoder
+            .decode(
+                Packet::try_from(&packet[..written]).unwrap(),
+                MutSignals::try_from(&mut output).unwrap(),
+            )
+            .unwrap();
+
+        assert_eq!(samples, 960);
+    }
+}
@@ -0,0 +1,118 @@
+//! Error- and result-types used throughout this crate.
+use crate::ffi;
+use std::{convert::TryFrom, fmt};
+
+/// Shorthand for `std::result::Result<T, audiopus::Error>`, returned by
+/// virtually every fallible function in this crate.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Represents every way a call into this crate can fail, be it a value this
+/// crate rejected before ever touching Opus, or an error-code Opus itself
+/// returned.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Error {
+    /// Opus itself reported failure, see [`ErrorCode`] for the concrete reason.
+    Opus(ErrorCode),
+    /// An application-value did not map to one of [`Application`]'s variants.
+    ///
+    /// [`Application`]: crate::Application
+    InvalidApplication,
+    /// A bandwidth-value did not map to one of [`Bandwidth`]'s variants.
+    ///
+    /// [`Bandwidth`]: crate::Bandwidth
+    InvalidBandwidth(i32),
+    /// A channels-value did not map to one of [`Channels`]'s variants.
+    ///
+    /// [`Channels`]: crate::Channels
+    InvalidChannels(i32),
+    /// A sample-rate-value did not map to one of [`SampleRate`]'s variants.
+    ///
+    /// [`SampleRate`]: crate::SampleRate
+    InvalidSampleRate(i32),
+    /// A signal-value did not map to one of [`Signal`]'s variants.
+    ///
+    /// [`Signal`]: crate::Signal
+    InvalidSignal(i32),
+    /// A packet was empty. Opus never accepts empty packets.
+    EmptyPacket,
+    /// A buffer or packet was longer than [`std::i32::MAX`], Opus does not
+    /// know any length-type larger than `i32`.
+    SignalsTooLarge,
+    /// A value was outside the range Opus documents for it, e.g. a
+    /// percentage passed as anything but `0..=100`.
+    InvalidArgument,
+    /// An `OpusHead` or `OpusTags` packet was malformed, see
+    /// [`ogg`](crate::ogg).
+    InvalidOggHeader,
+    /// An Ogg page was malformed: its capture pattern did not read `OggS`,
+    /// its checksum did not match, or it could not be read in full.
+    InvalidOggPage,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Opus(code) => write!(f, "opus returned an error: {:?}", code),
+            Error::InvalidApplication => write!(f, "not a valid opus application value"),
+            Error::InvalidBandwidth(v) => write!(f, "{} is not a valid opus bandwidth value", v),
+            Error::InvalidChannels(v) => write!(f, "{} is not a valid opus channels value", v),
+            Error::InvalidSampleRate(v) => write!(f, "{} is not a valid opus sample rate", v),
+            Error::InvalidSignal(v) => write!(f, "{} is not a valid opus signal value", v),
+            Error::EmptyPacket => write!(f, "opus packets must not be empty"),
+            Error::SignalsTooLarge => write!(f, "buffer is longer than i32::MAX"),
+            Error::InvalidArgument => write!(f, "argument is outside of the range opus accepts"),
+            Error::InvalidOggHeader => write!(f, "malformed OpusHead/OpusTags packet"),
+            Error::InvalidOggPage => write!(f, "malformed Ogg page"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Error-codes as returned by libopus itself, see `opus_defines.h`.
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ErrorCode {
+    /// One or more invalid/out of range arguments.
+    BadArg = ffi::OPUS_BAD_ARG,
+    /// Not enough bytes allocated in the buffer.
+    BufferTooSmall = ffi::OPUS_BUFFER_TOO_SMALL,
+    /// An internal error was detected.
+    InternalError = ffi::OPUS_INTERNAL_ERROR,
+    /// The compressed data passed is corrupted.
+    InvalidPacket = ffi::OPUS_INVALID_PACKET,
+    /// Invalid/unsupported request number.
+    Unimplemented = ffi::OPUS_UNIMPLEMENTED,
+    /// An encoder or decoder structure is invalid or already freed.
+    InvalidState = ffi::OPUS_INVALID_STATE,
+    /// Memory allocation has failed.
+    AllocFail = ffi::OPUS_ALLOC_FAIL,
+}
+
+impl TryFrom<i32> for ErrorCode {
+    type Error = Error;
+
+    fn try_from(value: i32) -> Result<Self> {
+        Ok(match value {
+            ffi::OPUS_BAD_ARG => ErrorCode::BadArg,
+            ffi::OPUS_BUFFER_TOO_SMALL => ErrorCode::BufferTooSmall,
+            ffi::OPUS_INTERNAL_ERROR => ErrorCode::InternalError,
+            ffi::OPUS_INVALID_PACKET => ErrorCode::InvalidPacket,
+            ffi::OPUS_UNIMPLEMENTED => ErrorCode::Unimplemented,
+            ffi::OPUS_INVALID_STATE => ErrorCode::InvalidState,
+            ffi::OPUS_ALLOC_FAIL => ErrorCode::AllocFail,
+            _ => ErrorCode::InternalError,
+        })
+    }
+}
+
+/// Turns a raw return-value from Opus into a [`Result`], mapping negative
+/// (error) values through [`ErrorCode`] and keeping non-negative values
+/// (often a length or another meaningful number) as-is.
+pub(crate) fn check(code: i32) -> Result<i32> {
+    if code < 0 {
+        Err(Error::Opus(ErrorCode::try_from(code)?))
+    } else {
+        Ok(code)
+    }
+}
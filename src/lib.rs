@@ -39,7 +39,10 @@
 // #![deny(missing_docs)]
 
 pub mod coder;
+#[cfg(feature = "custom")]
+pub mod custom;
 pub mod error;
+pub mod ogg;
 pub mod packet;
 pub mod repacketizer;
 pub mod softclip;
@@ -251,6 +254,51 @@ impl TryFrom<i32> for Bandwidth {
     }
 }
 
+/// Represents the frame durations an [`Encoder`] can be pinned to via
+/// [`Encoder::set_expert_frame_duration`].
+///
+/// [`Encoder`]: crate::coder::Encoder
+/// [`Encoder::set_expert_frame_duration`]: crate::coder::Encoder::set_expert_frame_duration
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum FrameDuration {
+    /// Pick the frame duration automatically.
+    Auto = ffi::OPUS_FRAMESIZE_ARG,
+    Ms2_5 = ffi::OPUS_FRAMESIZE_2_5_MS,
+    Ms5 = ffi::OPUS_FRAMESIZE_5_MS,
+    Ms10 = ffi::OPUS_FRAMESIZE_10_MS,
+    Ms20 = ffi::OPUS_FRAMESIZE_20_MS,
+    Ms40 = ffi::OPUS_FRAMESIZE_40_MS,
+    Ms60 = ffi::OPUS_FRAMESIZE_60_MS,
+    /// Only available in "expert" CELT-only mode.
+    Ms80 = ffi::OPUS_FRAMESIZE_80_MS,
+    /// Only available in "expert" CELT-only mode.
+    Ms100 = ffi::OPUS_FRAMESIZE_100_MS,
+    /// Only available in "expert" CELT-only mode.
+    Ms120 = ffi::OPUS_FRAMESIZE_120_MS,
+}
+
+impl TryFrom<i32> for FrameDuration {
+    type Error = Error;
+
+    /// Fails if a value does not match Opus' specified frame-size-value.
+    fn try_from(value: i32) -> Result<Self> {
+        Ok(match value {
+            ffi::OPUS_FRAMESIZE_ARG => FrameDuration::Auto,
+            ffi::OPUS_FRAMESIZE_2_5_MS => FrameDuration::Ms2_5,
+            ffi::OPUS_FRAMESIZE_5_MS => FrameDuration::Ms5,
+            ffi::OPUS_FRAMESIZE_10_MS => FrameDuration::Ms10,
+            ffi::OPUS_FRAMESIZE_20_MS => FrameDuration::Ms20,
+            ffi::OPUS_FRAMESIZE_40_MS => FrameDuration::Ms40,
+            ffi::OPUS_FRAMESIZE_60_MS => FrameDuration::Ms60,
+            ffi::OPUS_FRAMESIZE_80_MS => FrameDuration::Ms80,
+            ffi::OPUS_FRAMESIZE_100_MS => FrameDuration::Ms100,
+            ffi::OPUS_FRAMESIZE_120_MS => FrameDuration::Ms120,
+            _ => return Err(Error::InvalidArgument),
+        })
+    }
+}
+
 /// A newtype wrapping around a mutable buffer. They represent mutably borrowed
 /// arguments that will be filled by Opus.
 /// E.g. you pass this to an encode-method and Opus encodes data into the
@@ -0,0 +1,737 @@
+//! Reads and writes the standard Ogg Opus encapsulation ([RFC 7845]), so
+//! callers can produce and consume `.opus` files directly from the
+//! [`Packet`]s this crate encodes/decodes.
+//!
+//! This is a minimal, self-contained Ogg muxer/demuxer: it only understands
+//! enough of the Ogg bitstream format (page framing, segment lacing and the
+//! CRC libogg uses) to carry a single Opus logical stream, it is not a
+//! general-purpose Ogg implementation.
+//!
+//! [RFC 7845]: https://tools.ietf.org/html/rfc7845
+use crate::error::{Error, Result};
+use std::io::{self, Read, Write};
+
+const CAPTURE_PATTERN: &[u8; 4] = b"OggS";
+const HEADER_CONTINUED: u8 = 0x01;
+const HEADER_BOS: u8 = 0x02;
+const HEADER_EOS: u8 = 0x04;
+const MAX_SEGMENTS: usize = 255;
+const MAX_LACING_VALUE: usize = 255;
+
+/// The `OpusHead` identification header, the first packet of every Ogg Opus
+/// logical stream.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OpusHead {
+    pub channels: u8,
+    /// Number of samples (at 48kHz) to discard from the beginning of the
+    /// decoded output, compensating for the encoder's lookahead.
+    pub pre_skip: u16,
+    /// The original sample rate of the input before encoding, for
+    /// informational purposes only; Opus always decodes at 48kHz internally.
+    pub input_sample_rate: u32,
+    /// Output gain to apply, as a Q7.8 fixed-point number of dB.
+    pub output_gain: i16,
+    /// `0` for mono/stereo, `1` for the Vorbis channel order (up to 8
+    /// channels), `255` for an application-defined mapping.
+    pub mapping_family: u8,
+    /// Only meaningful when `mapping_family != 0`.
+    pub stream_count: u8,
+    /// Only meaningful when `mapping_family != 0`.
+    pub coupled_count: u8,
+    /// Only meaningful when `mapping_family != 0`, one entry per channel.
+    pub channel_mapping: Vec<u8>,
+}
+
+impl OpusHead {
+    /// Serialises this header into the `OpusHead` packet Opus decoders
+    /// expect as the first packet of a logical stream.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(19 + self.channel_mapping.len());
+
+        buf.extend_from_slice(b"OpusHead");
+        buf.push(1); // version
+        buf.push(self.channels);
+        buf.extend_from_slice(&self.pre_skip.to_le_bytes());
+        buf.extend_from_slice(&self.input_sample_rate.to_le_bytes());
+        buf.extend_from_slice(&self.output_gain.to_le_bytes());
+        buf.push(self.mapping_family);
+
+        if self.mapping_family != 0 {
+            buf.push(self.stream_count);
+            buf.push(self.coupled_count);
+            buf.extend_from_slice(&self.channel_mapping);
+        }
+
+        buf
+    }
+
+    /// Parses an `OpusHead` packet.
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        if data.len() < 19 || &data[0..8] != b"OpusHead" || data[8] != 1 {
+            return Err(Error::InvalidOggHeader);
+        }
+
+        let channels = data[9];
+        let pre_skip = u16::from_le_bytes([data[10], data[11]]);
+        let input_sample_rate = u32::from_le_bytes([data[12], data[13], data[14], data[15]]);
+        let output_gain = i16::from_le_bytes([data[16], data[17]]);
+        let mapping_family = data[18];
+
+        let (stream_count, coupled_count, channel_mapping) = if mapping_family == 0 {
+            (1, u8::from(channels == 2), Vec::new())
+        } else {
+            if data.len() < 21 + channels as usize {
+                return Err(Error::InvalidOggHeader);
+            }
+
+            (
+                data[19],
+                data[20],
+                data[21..21 + channels as usize].to_vec(),
+            )
+        };
+
+        Ok(Self {
+            channels,
+            pre_skip,
+            input_sample_rate,
+            output_gain,
+            mapping_family,
+            stream_count,
+            coupled_count,
+            channel_mapping,
+        })
+    }
+}
+
+/// The `OpusTags` comment header, the second packet of every Ogg Opus
+/// logical stream.
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct OpusTags {
+    pub vendor: String,
+    pub comments: Vec<String>,
+}
+
+impl OpusTags {
+    /// Serialises this header into the `OpusTags` packet Opus decoders
+    /// expect as the second packet of a logical stream.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+
+        buf.extend_from_slice(b"OpusTags");
+        buf.extend_from_slice(&(self.vendor.len() as u32).to_le_bytes());
+        buf.extend_from_slice(self.vendor.as_bytes());
+        buf.extend_from_slice(&(self.comments.len() as u32).to_le_bytes());
+
+        for comment in &self.comments {
+            buf.extend_from_slice(&(comment.len() as u32).to_le_bytes());
+            buf.extend_from_slice(comment.as_bytes());
+        }
+
+        buf
+    }
+
+    /// Parses an `OpusTags` packet.
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let mut reader = ByteReader(data);
+
+        if reader.take(8).ok_or(Error::InvalidOggHeader)? != b"OpusTags" {
+            return Err(Error::InvalidOggHeader);
+        }
+
+        let vendor = reader.take_string().ok_or(Error::InvalidOggHeader)?;
+        let comment_count = reader.take_u32().ok_or(Error::InvalidOggHeader)?;
+        let mut comments = Vec::with_capacity(comment_count as usize);
+
+        for _ in 0..comment_count {
+            comments.push(reader.take_string().ok_or(Error::InvalidOggHeader)?);
+        }
+
+        Ok(Self { vendor, comments })
+    }
+}
+
+struct ByteReader<'a>(&'a [u8]);
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.0.len() < len {
+            return None;
+        }
+
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Some(head)
+    }
+
+    fn take_u32(&mut self) -> Option<u32> {
+        let bytes = self.take(4)?;
+        Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn take_string(&mut self) -> Option<String> {
+        let len = self.take_u32()? as usize;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec()).ok()
+    }
+}
+
+/// Computes the CRC-32 variant libogg stamps into every page's checksum
+/// field (polynomial `0x04c11db7`, not reflected, zero-initialised).
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc: u32 = 0;
+
+    for &byte in data {
+        crc ^= u32::from(byte) << 24;
+
+        for _ in 0..8 {
+            crc = if crc & 0x8000_0000 != 0 {
+                (crc << 1) ^ 0x04c1_1db7
+            } else {
+                crc << 1
+            };
+        }
+    }
+
+    crc
+}
+
+/// Splits a packet length into the lacing values (Ogg "segments") that
+/// describe it, per the Ogg bitstream specification: `len / 255` segments
+/// of `255`, followed by one final segment of `len % 255` (even if that is
+/// `0`, which is what distinguishes an exact multiple of 255 from a packet
+/// that continues onto the next page).
+fn lacing_values(len: usize) -> Vec<u8> {
+    let mut segments = vec![MAX_LACING_VALUE as u8; len / MAX_LACING_VALUE];
+    segments.push((len % MAX_LACING_VALUE) as u8);
+    segments
+}
+
+/// Writes Opus packets out as a sequence of Ogg pages making up one logical
+/// Ogg Opus stream.
+#[derive(Debug)]
+pub struct Writer<W> {
+    writer: W,
+    serial: u32,
+    sequence: u32,
+    granule_position: u64,
+    segments: Vec<u8>,
+    data: Vec<u8>,
+    wrote_headers: bool,
+    /// Whether the currently buffered page's first packet continues a
+    /// packet whose lacing values overflowed the previous page.
+    continued: bool,
+}
+
+impl<W: Write> Writer<W> {
+    /// Creates a writer and immediately emits the `OpusHead`/`OpusTags`
+    /// header pages. `serial` is the Ogg logical bitstream serial number,
+    /// callers should pick one at random per RFC 3533.
+    pub fn new(mut writer: W, serial: u32, head: &OpusHead, tags: &OpusTags) -> io::Result<Self> {
+        let head_bytes = head.to_bytes();
+        let tags_bytes = tags.to_bytes();
+
+        write_page(
+            &mut writer,
+            HEADER_BOS,
+            0,
+            serial,
+            0,
+            &segment_page(&lacing_values(head_bytes.len()), &head_bytes),
+        )?;
+        write_page(
+            &mut writer,
+            0,
+            0,
+            serial,
+            1,
+            &segment_page(&lacing_values(tags_bytes.len()), &tags_bytes),
+        )?;
+
+        Ok(Self {
+            writer,
+            serial,
+            sequence: 2,
+            granule_position: 0,
+            segments: Vec::new(),
+            data: Vec::new(),
+            wrote_headers: true,
+            continued: false,
+        })
+    }
+
+    /// Queues an encoded Opus packet for the current page, flushing a page
+    /// to the underlying writer whenever the pending packets would no
+    /// longer fit into a single page's 255-segment budget.
+    ///
+    /// Packets whose lacing values don't fit into a single page's
+    /// 255-segment table (i.e. 65,025 bytes or longer) are split across
+    /// as many full continuation pages as needed, per [RFC 7845]'s
+    /// `HEADER_CONTINUED` framing.
+    ///
+    /// `samples` is the number of 48kHz-equivalent samples this packet
+    /// decodes to, used to advance the page's granule position.
+    ///
+    /// [RFC 7845]: https://tools.ietf.org/html/rfc7845
+    pub fn write_packet(&mut self, packet: &[u8], samples: u32) -> io::Result<()> {
+        debug_assert!(self.wrote_headers);
+
+        let mut remaining = packet;
+
+        while lacing_values(remaining.len()).len() > MAX_SEGMENTS {
+            self.flush_page(0)?;
+
+            let (chunk, rest) = remaining.split_at(MAX_SEGMENTS * MAX_LACING_VALUE);
+
+            self.segments
+                .extend(std::iter::repeat_n(MAX_LACING_VALUE as u8, MAX_SEGMENTS));
+            self.data.extend_from_slice(chunk);
+            self.flush_page(0)?;
+            self.continued = true;
+
+            remaining = rest;
+        }
+
+        let lacing = lacing_values(remaining.len());
+
+        if self.segments.len() + lacing.len() > MAX_SEGMENTS {
+            self.flush_page(0)?;
+        }
+
+        self.segments.extend_from_slice(&lacing);
+        self.data.extend_from_slice(remaining);
+        self.granule_position += u64::from(samples);
+
+        Ok(())
+    }
+
+    /// Flushes any queued packets as a final page marked end-of-stream.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.flush_page(HEADER_EOS)?;
+        Ok(self.writer)
+    }
+
+    fn flush_page(&mut self, extra_header_type: u8) -> io::Result<()> {
+        if self.segments.is_empty() && extra_header_type & HEADER_EOS == 0 {
+            return Ok(());
+        }
+
+        let header_type = extra_header_type | if self.continued { HEADER_CONTINUED } else { 0 };
+
+        write_page(
+            &mut self.writer,
+            header_type,
+            self.granule_position as i64,
+            self.serial,
+            self.sequence,
+            &segment_page(&self.segments, &self.data),
+        )?;
+
+        self.sequence += 1;
+        self.segments.clear();
+        self.data.clear();
+        self.continued = false;
+
+        Ok(())
+    }
+}
+
+/// Lays out a page's segment table followed by its packet data, the shape
+/// [`write_page`] expects for its `body` argument.
+fn segment_page(segments: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut body = Vec::with_capacity(1 + segments.len() + data.len());
+    body.push(segments.len() as u8);
+    body.extend_from_slice(segments);
+    body.extend_from_slice(data);
+    body
+}
+
+fn write_page<W: Write>(
+    writer: &mut W,
+    header_type: u8,
+    granule_position: i64,
+    serial: u32,
+    sequence: u32,
+    segmented_body: &[u8],
+) -> io::Result<()> {
+    let mut page = Vec::with_capacity(27 + segmented_body.len());
+
+    page.extend_from_slice(CAPTURE_PATTERN);
+    page.push(0); // version
+    page.push(header_type);
+    page.extend_from_slice(&granule_position.to_le_bytes());
+    page.extend_from_slice(&serial.to_le_bytes());
+    page.extend_from_slice(&sequence.to_le_bytes());
+    page.extend_from_slice(&0u32.to_le_bytes()); // checksum, filled in below
+    page.extend_from_slice(segmented_body);
+
+    let checksum = crc32(&page);
+    page[22..26].copy_from_slice(&checksum.to_le_bytes());
+
+    writer.write_all(&page)
+}
+
+/// A single Ogg page, as read off the wire by [`Reader`].
+struct Page {
+    header_type: u8,
+    packets: Vec<Vec<u8>>,
+    /// Whether the last packet in `packets` is continued on the next page.
+    incomplete_tail: bool,
+}
+
+fn read_page<R: Read>(reader: &mut R) -> io::Result<Option<Page>> {
+    let mut header = [0u8; 27];
+
+    if !read_exact_or_eof(reader, &mut header)? {
+        return Ok(None);
+    }
+
+    if &header[0..4] != CAPTURE_PATTERN {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, Error::InvalidOggPage));
+    }
+
+    let header_type = header[5];
+    let checksum = u32::from_le_bytes([header[22], header[23], header[24], header[25]]);
+    let page_segments = header[26] as usize;
+
+    let mut segment_table = vec![0u8; page_segments];
+    reader.read_exact(&mut segment_table)?;
+
+    let body_len: usize = segment_table.iter().map(|&len| len as usize).sum();
+    let mut data = vec![0u8; body_len];
+    reader.read_exact(&mut data)?;
+
+    let mut crc_input = Vec::with_capacity(header.len() + segment_table.len() + data.len());
+    crc_input.extend_from_slice(&header[..22]);
+    crc_input.extend_from_slice(&0u32.to_le_bytes());
+    crc_input.push(header[26]);
+    crc_input.extend_from_slice(&segment_table);
+    crc_input.extend_from_slice(&data);
+
+    if crc32(&crc_input) != checksum {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, Error::InvalidOggPage));
+    }
+
+    let mut packets = Vec::new();
+    let mut current = Vec::new();
+    let mut incomplete_tail = false;
+    let mut offset = 0;
+
+    for &segment_len in &segment_table {
+        current.extend_from_slice(&data[offset..offset + segment_len as usize]);
+        offset += segment_len as usize;
+
+        if (segment_len as usize) < MAX_LACING_VALUE {
+            packets.push(std::mem::take(&mut current));
+            incomplete_tail = false;
+        } else {
+            incomplete_tail = true;
+        }
+    }
+
+    if incomplete_tail {
+        packets.push(current);
+    }
+
+    Ok(Some(Page {
+        header_type,
+        packets,
+        incomplete_tail,
+    }))
+}
+
+fn read_exact_or_eof<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<bool> {
+    let mut read = 0;
+
+    while read < buf.len() {
+        match reader.read(&mut buf[read..])? {
+            0 if read == 0 => return Ok(false),
+            0 => return Err(io::ErrorKind::UnexpectedEof.into()),
+            n => read += n,
+        }
+    }
+
+    Ok(true)
+}
+
+/// Reads Ogg pages off an Ogg Opus logical stream and yields the Opus
+/// packets they carry.
+#[derive(Debug)]
+pub struct Reader<R> {
+    reader: R,
+    head: OpusHead,
+    tags: OpusTags,
+    pending: std::collections::VecDeque<Vec<u8>>,
+    /// Carries a packet split across a page boundary until its continuation
+    /// arrives.
+    partial: Option<Vec<u8>>,
+    finished: bool,
+}
+
+impl<R: Read> Reader<R> {
+    /// Creates a reader, parsing the `OpusHead`/`OpusTags` header pages.
+    pub fn new(mut reader: R) -> Result<Self> {
+        let head_page = read_page(&mut reader)
+            .map_err(|_| Error::InvalidOggPage)?
+            .ok_or(Error::InvalidOggPage)?;
+        let head = OpusHead::from_bytes(
+            head_page.packets.first().ok_or(Error::InvalidOggHeader)?,
+        )?;
+
+        let tags_page = read_page(&mut reader)
+            .map_err(|_| Error::InvalidOggPage)?
+            .ok_or(Error::InvalidOggPage)?;
+        let tags = OpusTags::from_bytes(
+            tags_page.packets.first().ok_or(Error::InvalidOggHeader)?,
+        )?;
+
+        Ok(Self {
+            reader,
+            head,
+            tags,
+            pending: std::collections::VecDeque::new(),
+            partial: None,
+            finished: false,
+        })
+    }
+
+    pub fn head(&self) -> &OpusHead {
+        &self.head
+    }
+
+    pub fn tags(&self) -> &OpusTags {
+        &self.tags
+    }
+
+    /// Reads the next Opus packet, or `Ok(None)` once the stream is
+    /// exhausted.
+    pub fn next_packet(&mut self) -> io::Result<Option<Vec<u8>>> {
+        loop {
+            if let Some(packet) = self.pending.pop_front() {
+                return Ok(Some(packet));
+            }
+
+            if self.finished {
+                return Ok(None);
+            }
+
+            let page = match read_page(&mut self.reader)? {
+                Some(page) => page,
+                None => {
+                    self.finished = true;
+                    return Ok(self.partial.take());
+                }
+            };
+
+            let mut packets = page.packets;
+
+            if header_type_continued(page.header_type) {
+                if let (Some(partial), Some(first)) = (self.partial.take(), packets.first_mut()) {
+                    let mut joined = partial;
+                    joined.append(first);
+                    *first = joined;
+                }
+            }
+
+            if page.incomplete_tail {
+                self.partial = packets.pop();
+            }
+
+            self.pending.extend(packets);
+
+            if page.header_type & HEADER_EOS != 0 {
+                self.finished = true;
+            }
+        }
+    }
+}
+
+fn header_type_continued(header_type: u8) -> bool {
+    header_type & HEADER_CONTINUED != 0
+}
+
+impl<R: Read> Iterator for Reader<R> {
+    type Item = io::Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_packet().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OpusHead, OpusTags, Reader, Writer};
+    use std::io;
+
+    #[test]
+    fn opus_head_round_trip_family_0() {
+        let head = OpusHead {
+            channels: 2,
+            pre_skip: 312,
+            input_sample_rate: 44100,
+            output_gain: 0,
+            mapping_family: 0,
+            stream_count: 1,
+            coupled_count: 1,
+            channel_mapping: Vec::new(),
+        };
+
+        let parsed = OpusHead::from_bytes(&head.to_bytes()).unwrap();
+
+        assert_eq!(head, parsed);
+    }
+
+    #[test]
+    fn opus_head_round_trip_family_1() {
+        let head = OpusHead {
+            channels: 6,
+            pre_skip: 0,
+            input_sample_rate: 48000,
+            output_gain: 0,
+            mapping_family: 1,
+            stream_count: 4,
+            coupled_count: 2,
+            channel_mapping: vec![0, 4, 1, 2, 3, 5],
+        };
+
+        let parsed = OpusHead::from_bytes(&head.to_bytes()).unwrap();
+
+        assert_eq!(head, parsed);
+    }
+
+    #[test]
+    fn opus_tags_round_trip() {
+        let tags = OpusTags {
+            vendor: "audiopus".to_owned(),
+            comments: vec!["TITLE=test".to_owned(), "ARTIST=nobody".to_owned()],
+        };
+
+        let parsed = OpusTags::from_bytes(&tags.to_bytes()).unwrap();
+
+        assert_eq!(tags, parsed);
+    }
+
+    #[test]
+    fn write_then_read_round_trip() {
+        let head = OpusHead {
+            channels: 1,
+            pre_skip: 120,
+            input_sample_rate: 48000,
+            output_gain: 0,
+            mapping_family: 0,
+            stream_count: 1,
+            coupled_count: 0,
+            channel_mapping: Vec::new(),
+        };
+        let tags = OpusTags {
+            vendor: "audiopus".to_owned(),
+            comments: Vec::new(),
+        };
+
+        let packets: &[&[u8]] = &[&[1, 2, 3], &[4, 5, 6, 7], &[8; 512]];
+
+        let mut writer = Writer::new(Vec::new(), 0x1234_5678, &head, &tags).unwrap();
+
+        for packet in packets {
+            writer.write_packet(packet, 960).unwrap();
+        }
+
+        let buf = writer.finish().unwrap();
+
+        let mut reader = Reader::new(buf.as_slice()).unwrap();
+
+        assert_eq!(reader.head(), &head);
+        assert_eq!(reader.tags(), &tags);
+
+        let read_packets: Vec<Vec<u8>> = reader.by_ref().collect::<io::Result<_>>().unwrap();
+
+        assert_eq!(
+            read_packets,
+            packets.iter().map(|p| p.to_vec()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn write_then_read_oversized_packet_spans_pages() {
+        let head = OpusHead {
+            channels: 1,
+            pre_skip: 120,
+            input_sample_rate: 48000,
+            output_gain: 0,
+            mapping_family: 0,
+            stream_count: 1,
+            coupled_count: 0,
+            channel_mapping: Vec::new(),
+        };
+        let tags = OpusTags::default();
+
+        // Longer than one full 255-segment page (65,025 bytes), so the rest
+        // continues onto a second page, to exercise the continuation-page
+        // splitting in `Writer::write_packet`.
+        let big_packet: Vec<u8> = (0..70_000).map(|i| (i % 256) as u8).collect();
+        let packets: &[&[u8]] = &[&[1, 2, 3], &big_packet, &[4, 5, 6, 7]];
+
+        let mut writer = Writer::new(Vec::new(), 0x1234_5678, &head, &tags).unwrap();
+
+        for packet in packets {
+            writer.write_packet(packet, 960).unwrap();
+        }
+
+        let buf = writer.finish().unwrap();
+
+        let mut reader = Reader::new(buf.as_slice()).unwrap();
+        let read_packets: Vec<Vec<u8>> = reader.by_ref().collect::<io::Result<_>>().unwrap();
+
+        assert_eq!(
+            read_packets,
+            packets.iter().map(|p| p.to_vec()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn read_rejects_page_with_corrupted_crc() {
+        let head = OpusHead {
+            channels: 1,
+            pre_skip: 120,
+            input_sample_rate: 48000,
+            output_gain: 0,
+            mapping_family: 0,
+            stream_count: 1,
+            coupled_count: 0,
+            channel_mapping: Vec::new(),
+        };
+        let tags = OpusTags::default();
+
+        let mut writer = Writer::new(Vec::new(), 0x1234_5678, &head, &tags).unwrap();
+        writer.write_packet(&[1, 2, 3], 960).unwrap();
+        let mut buf = writer.finish().unwrap();
+
+        // Flip a bit in the last page's payload, leaving its checksum stale.
+        let last = buf.len() - 1;
+        buf[last] ^= 0xff;
+
+        let mut reader = Reader::new(buf.as_slice()).unwrap();
+        let err = reader.next_packet().unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn opus_head_rejects_unsupported_version() {
+        let head = OpusHead {
+            channels: 2,
+            pre_skip: 0,
+            input_sample_rate: 48000,
+            output_gain: 0,
+            mapping_family: 0,
+            stream_count: 1,
+            coupled_count: 1,
+            channel_mapping: Vec::new(),
+        };
+
+        let mut bytes = head.to_bytes();
+        bytes[8] = 2;
+
+        assert!(OpusHead::from_bytes(&bytes).is_err());
+    }
+}
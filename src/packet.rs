@@ -0,0 +1,114 @@
+//! Newtypes wrapping the buffers passed to/from Opus as encoded packets,
+//! ensuring Opus' invariants about packets are upheld before we ever call
+//! into it.
+use crate::error::{Error, Result};
+use std::convert::{TryFrom, TryInto};
+
+/// Represents an immutably borrowed, already encoded Opus-packet.
+///
+/// A [`Packet`] can neither be empty nor longer than [`std::i32::MAX`]
+/// bytes, both are enforced on construction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Packet<'a>(&'a [u8]);
+
+impl<'a> Packet<'a> {
+    pub fn as_ptr(&self) -> *const u8 {
+        self.0.as_ptr()
+    }
+
+    /// Due to checking the length during construction of this newtype, we
+    /// can safely cast `usize` to `i32` without worrying about `usize`
+    /// being too large for `i32`.
+    pub fn i32_len(&self) -> i32 {
+        self.0.len() as i32
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Always `false`, a [`Packet`] can never be empty.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for Packet<'a> {
+    type Error = Error;
+
+    fn try_from(value: &'a [u8]) -> Result<Self> {
+        if value.is_empty() {
+            return Err(Error::EmptyPacket);
+        }
+
+        if value.len() > std::i32::MAX as usize {
+            return Err(Error::SignalsTooLarge);
+        }
+
+        Ok(Self(value))
+    }
+}
+
+impl<'a> TryFrom<&'a Vec<u8>> for Packet<'a> {
+    type Error = Error;
+
+    fn try_from(value: &'a Vec<u8>) -> Result<Self> {
+        value.as_slice().try_into()
+    }
+}
+
+/// Represents a mutably borrowed buffer Opus will write an encoded packet
+/// into, e.g. via [`Repacketizer::out`].
+///
+/// [`Repacketizer::out`]: crate::repacketizer::Repacketizer::out
+///
+/// Like [`Packet`], a [`MutPacket`] can neither be empty nor longer than
+/// [`std::i32::MAX`] bytes.
+#[derive(Debug)]
+pub struct MutPacket<'a>(&'a mut [u8]);
+
+impl<'a> MutPacket<'a> {
+    pub fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.0.as_mut_ptr()
+    }
+
+    /// Due to checking the length during construction of this newtype, we
+    /// can safely cast `usize` to `i32` without worrying about `usize`
+    /// being too large for `i32`.
+    pub fn i32_len(&self) -> i32 {
+        self.0.len() as i32
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Always `false`, a [`MutPacket`] can never be empty.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+impl<'a> TryFrom<&'a mut [u8]> for MutPacket<'a> {
+    type Error = Error;
+
+    fn try_from(value: &'a mut [u8]) -> Result<Self> {
+        if value.is_empty() {
+            return Err(Error::EmptyPacket);
+        }
+
+        if value.len() > std::i32::MAX as usize {
+            return Err(Error::SignalsTooLarge);
+        }
+
+        Ok(Self(value))
+    }
+}
+
+impl<'a> TryFrom<&'a mut Vec<u8>> for MutPacket<'a> {
+    type Error = Error;
+
+    fn try_from(value: &'a mut Vec<u8>) -> Result<Self> {
+        value.as_mut_slice().try_into()
+    }
+}
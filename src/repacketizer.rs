@@ -0,0 +1,70 @@
+//! Merges and splits Opus packets without touching the encoded audio,
+//! wrapping libopus' `opus_repacketizer_*` API.
+use crate::{
+    error::{check, Error, ErrorCode, Result},
+    ffi,
+    packet::{MutPacket, Packet},
+};
+
+/// Combines multiple Opus packets into one, or splits one packet into
+/// several, as long as all packets share the same configuration (sample
+/// rate, channel count and frame size).
+#[derive(Debug)]
+pub struct Repacketizer {
+    pointer: *mut ffi::OpusRepacketizer,
+}
+
+unsafe impl Send for Repacketizer {}
+
+impl Repacketizer {
+    /// Creates a new repacketizer state.
+    pub fn new() -> Result<Self> {
+        let pointer = unsafe { ffi::opus_repacketizer_create() };
+
+        if pointer.is_null() {
+            return Err(Error::Opus(ErrorCode::AllocFail));
+        }
+
+        Ok(Self { pointer })
+    }
+
+    /// Discards any packets added via [`cat`](Self::cat) so far, allowing
+    /// this repacketizer to be reused for another group of packets.
+    pub fn reset(&mut self) {
+        unsafe {
+            ffi::opus_repacketizer_init(self.pointer);
+        }
+    }
+
+    /// Adds a packet to the current group, it must use the same
+    /// configuration as any packet previously added to this group.
+    pub fn cat(&mut self, packet: Packet<'_>) -> Result<()> {
+        check(unsafe {
+            ffi::opus_repacketizer_cat(self.pointer, packet.as_ptr(), packet.i32_len())
+        })?;
+
+        Ok(())
+    }
+
+    /// Returns the total number of frames contained in the packets added to
+    /// the current group so far.
+    pub fn nb_frames(&self) -> i32 {
+        unsafe { ffi::opus_repacketizer_get_nb_frames(self.pointer) }
+    }
+
+    /// Writes out the packets added to the current group so far as a
+    /// single packet, returning the number of bytes written.
+    pub fn out(&mut self, mut output: MutPacket<'_>) -> Result<i32> {
+        check(unsafe {
+            ffi::opus_repacketizer_out(self.pointer, output.as_mut_ptr(), output.i32_len())
+        })
+    }
+}
+
+impl Drop for Repacketizer {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::opus_repacketizer_destroy(self.pointer);
+        }
+    }
+}
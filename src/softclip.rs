@@ -0,0 +1,24 @@
+//! Soft-clipping of floating point PCM, avoiding harsh clipping artifacts
+//! when samples exceed the `[-1, 1]` range right before encoding.
+use crate::{ffi, MutSignals};
+
+/// Applies soft-clipping to a float signal, bringing it within the `[-1, 1]`
+/// range an [`Encoder`] expects without the harsh distortion a hard clip
+/// would introduce.
+///
+/// `channels` and `softclip_mem` must match the encoder this signal is
+/// destined for; `softclip_mem` carries clipping state across calls and
+/// should be initialised to all-zeroes and then reused for every frame of a
+/// stream.
+///
+/// [`Encoder`]: crate::coder::Encoder
+pub fn soft_clip(signal: &mut MutSignals<'_, f32>, channels: i32, softclip_mem: &mut [f32]) {
+    unsafe {
+        ffi::opus_pcm_soft_clip(
+            signal.as_mut_ptr(),
+            signal.i32_len() / channels,
+            channels,
+            softclip_mem.as_mut_ptr(),
+        );
+    }
+}